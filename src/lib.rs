@@ -6,10 +6,29 @@
 //! The simplest interface provided is the [`strip`] function, which takes a byte slice and returns
 //! a `Vec` of bytes with escape sequences removed. For writing bytes directly to a writer, you
 //! may prefer using the [`Writer`] struct, which implements `Write` and strips escape sequences
-//! as they are written.
+//! as they are written. For reading bytes from an existing source (such as a child process's
+//! output) with escape sequences removed on the fly, there is also a [`Reader`] struct which
+//! implements `Read`. If you're processing many small chunks of a stream and want to avoid
+//! allocating a fresh buffer for each one, [`StripBytes`] keeps its parser state between calls.
+//! If your input is already a `&str`, [`strip_str`] and [`StripStr`] work the same way while
+//! guaranteeing valid UTF-8 output.
+//!
+//! By default everything except printable text and `\n` is discarded, but [`WriterBuilder`]
+//! lets you opt back into preserving specific categories — C0 whitespace controls, OSC 8
+//! hyperlinks, and/or SGR color sequences — for pipelines that want some escapes to survive.
+//!
+//! On Windows consoles that don't interpret ANSI escapes themselves, enabling the
+//! `winconsole` feature adds the [`wincon`] module, which translates SGR color escapes into
+//! console attribute calls instead of discarding them.
 //!
 //! [`strip`]: fn.strip.html
 //! [`Writer`]: struct.Writer.html
+//! [`WriterBuilder`]: struct.WriterBuilder.html
+//! [`Reader`]: struct.Reader.html
+//! [`StripBytes`]: struct.StripBytes.html
+//! [`strip_str`]: fn.strip_str.html
+//! [`StripStr`]: struct.StripStr.html
+//! [`wincon`]: wincon/index.html
 //!
 //! # Example
 //!
@@ -26,9 +45,12 @@
 
 extern crate vte;
 
-use std::io::{self, Cursor, IntoInnerError, LineWriter, Write};
+use std::io::{self, IntoInnerError, LineWriter, Read, Write};
 use vte::{Parser, Perform};
 
+#[cfg(feature = "winconsole")]
+pub mod wincon;
+
 /// `Writer` wraps an underlying type that implements `Write`, stripping ANSI escape sequences
 /// from bytes written to it before passing them to the underlying writer.
 ///
@@ -53,6 +75,98 @@ pub struct Writer<W>
     parser: Parser,
 }
 
+/// Which categories of otherwise-discarded escape sequences a [`Writer`] should instead pass
+/// through, re-emitted from what the parser reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Config {
+    preserve_c0_whitespace: bool,
+    preserve_osc8_hyperlinks: bool,
+    preserve_sgr: bool,
+}
+
+/// Builds a [`Writer`] that can be configured to preserve certain categories of escape
+/// sequences instead of stripping them unconditionally.
+///
+/// By default every option is off, matching [`Writer::new`]'s all-or-nothing behavior.
+///
+/// # Example
+/// ```
+/// use std::io;
+/// use strip_ansi_escapes::WriterBuilder;
+///
+/// # fn foo() -> io::Result<()> {
+/// let mut writer = WriterBuilder::new()
+///     .preserve_sgr(true)
+///     .build(io::stdout());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WriterBuilder {
+    config: Config,
+}
+
+impl WriterBuilder {
+    /// Create a new `WriterBuilder` with every preservation option off.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preserve `\t`, `\r`, and `\x0c` instead of discarding them like the other C0 control
+    /// bytes. `\n` is always kept regardless of this setting.
+    pub fn preserve_c0_whitespace(&mut self, yes: bool) -> &mut Self {
+        self.config.preserve_c0_whitespace = yes;
+        self
+    }
+
+    /// Preserve OSC 8 hyperlink escape sequences instead of discarding them.
+    ///
+    /// vte doesn't report how the original sequence was terminated, so the reassembled
+    /// escape is always normalized to a BEL (`\x07`) terminator, even if the input used the
+    /// two-byte ST (`\x1b\\`) form instead.
+    pub fn preserve_osc8_hyperlinks(&mut self, yes: bool) -> &mut Self {
+        self.config.preserve_osc8_hyperlinks = yes;
+        self
+    }
+
+    /// Preserve CSI SGR (color/style) escape sequences instead of discarding them.
+    ///
+    /// The sequence is reassembled from the numeric params vte reports, so an omitted param
+    /// comes out as an explicit `0`: a bare reset (`\x1b[m`) is re-emitted as `\x1b[0m`.
+    pub fn preserve_sgr(&mut self, yes: bool) -> &mut Self {
+        self.config.preserve_sgr = yes;
+        self
+    }
+
+    /// Build a [`Writer`] that writes to `inner`, using the options configured so far.
+    pub fn build<W>(&self, inner: W) -> Writer<W>
+        where W: Write,
+    {
+        Writer {
+            performer: Performer {
+                writer: LineWriter::new(inner),
+                err: None,
+                config: self.config,
+            },
+            parser: Parser::new(),
+        }
+    }
+
+    /// Strip `data` using the options configured so far, returning the remaining bytes.
+    ///
+    /// This is the configurable counterpart to the top-level [`strip`] function.
+    ///
+    /// [`strip`]: fn.strip.html
+    pub fn strip<T>(&self, data: T) -> io::Result<Vec<u8>>
+        where T: AsRef<[u8]>,
+    {
+        let mut writer = self.build(Vec::new());
+        writer.write_all(data.as_ref())?;
+        writer.flush()?;
+        writer.into_inner().map_err(IntoInnerError::into_error)
+    }
+}
+
 /// Strip ANSI escapes from `data` and return the remaining bytes as a `Vec<u8>`.
 ///
 /// See [the module documentation][mod] for an example.
@@ -61,10 +175,134 @@ pub struct Writer<W>
 pub fn strip<T>(data: T) -> io::Result<Vec<u8>>
     where T: AsRef<[u8]>,
 {
-    let c = Cursor::new(Vec::new());
-    let mut writer = Writer::new(c);
-    writer.write_all(data.as_ref())?;
-    Ok(writer.into_inner()?.into_inner())
+    let mut stripper = StripBytes::new();
+    Ok(stripper.strip_next(data.as_ref()).flatten().copied().collect())
+}
+
+/// Incrementally strips ANSI escapes from successive byte slices.
+///
+/// Unlike [`strip`], a `StripBytes` keeps its parser state across calls, so an escape
+/// sequence split across two fed chunks is still fully consumed, and it avoids allocating
+/// a fresh buffer on every call. This is useful when processing many small chunks, such as
+/// lines of a streaming log.
+///
+/// # Example
+/// ```
+/// use strip_ansi_escapes::StripBytes;
+///
+/// let mut stripper = StripBytes::new();
+/// for chunk in stripper.strip_next(b"\x1b[32mfoo\x1b[m bar") {
+///     assert_eq!(chunk, b"foo bar");
+/// }
+/// ```
+///
+/// [`strip`]: fn.strip.html
+pub struct StripBytes {
+    parser: Parser,
+    performer: Performer<Vec<u8>>,
+}
+
+impl Default for StripBytes {
+    fn default() -> Self {
+        StripBytes {
+            parser: Parser::new(),
+            performer: Performer {
+                writer: LineWriter::new(Vec::new()),
+                err: None,
+                config: Config::default(),
+            },
+        }
+    }
+}
+
+impl StripBytes {
+    /// Create a new, empty `StripBytes`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `bytes` through the parser, returning the printable runs that result.
+    ///
+    /// The parser's state (e.g. a partially-consumed escape sequence) is preserved between
+    /// calls, so the sequence still gets stripped correctly even if it's split across two
+    /// calls to `strip_next`.
+    pub fn strip_next<'a>(&'a mut self, bytes: &[u8]) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.performer.writer.get_mut().clear();
+        for b in bytes.iter() {
+            self.parser.advance(&mut self.performer, *b);
+        }
+        // Writes to a `Vec<u8>` never fail, so any flush error can only come from a
+        // previous call; there is none here, so this is infallible in practice.
+        let _ = self.performer.flush();
+        let stripped = self.performer.writer.get_ref().as_slice();
+        if stripped.is_empty() {
+            None.into_iter()
+        } else {
+            Some(stripped).into_iter()
+        }
+    }
+
+    /// Reset this `StripBytes` to its initial state, so it can be reused for an unrelated
+    /// stream.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Strip ANSI escapes from `s` and return the remaining text as a `String`.
+///
+/// Unlike [`strip`], this operates on `&str` and guarantees valid UTF-8 output, so there's no
+/// need to re-validate the result afterwards.
+///
+/// [`strip`]: fn.strip.html
+pub fn strip_str(s: &str) -> String {
+    let mut stripper = StripStr::new();
+    stripper.strip_next(s).to_owned()
+}
+
+/// Incrementally strips ANSI escapes from successive string slices.
+///
+/// This is the `&str` counterpart to [`StripBytes`]: stripping never splits a multi-byte
+/// `char`, since the parser's `print` hook already operates at the `char` level, so the
+/// output is always valid UTF-8 and doesn't need to be re-validated.
+///
+/// # Example
+/// ```
+/// use strip_ansi_escapes::StripStr;
+///
+/// let mut stripper = StripStr::new();
+/// assert_eq!(stripper.strip_next("\x1b[32mfoo\x1b[m bar"), "foo bar");
+/// ```
+///
+/// [`StripBytes`]: struct.StripBytes.html
+#[derive(Default)]
+pub struct StripStr {
+    inner: StripBytes,
+}
+
+impl StripStr {
+    /// Create a new, empty `StripStr`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `s` through the parser, returning the printable text that results.
+    ///
+    /// As with [`StripBytes::strip_next`], the parser's state is preserved between calls.
+    ///
+    /// [`StripBytes::strip_next`]: struct.StripBytes.html#method.strip_next
+    pub fn strip_next(&mut self, s: &str) -> &str {
+        let stripped = self.inner.strip_next(s.as_bytes()).next().unwrap_or(&[]);
+        // Safety: `Performer::print` only ever writes whole `char`s, and `execute` only
+        // ever writes the single-byte, ASCII `'\n'`, so `stripped` is always valid UTF-8.
+        unsafe { std::str::from_utf8_unchecked(stripped) }
+    }
+
+    /// Reset this `StripStr` to its initial state, so it can be reused for an unrelated
+    /// stream.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
 }
 
 struct Performer<W>
@@ -72,20 +310,18 @@ struct Performer<W>
 {
     writer: LineWriter<W>,
     err: Option<io::Error>,
+    config: Config,
 }
 
 impl<W> Writer<W>
     where W: Write,
 {
     /// Create a new `Writer` that writes to `inner`.
+    ///
+    /// To preserve some categories of escape sequences instead of discarding everything,
+    /// use [`WriterBuilder`] instead.
     pub fn new(inner: W) -> Writer<W> {
-        Writer {
-            performer: Performer {
-                writer: LineWriter::new(inner),
-                err: None,
-            },
-            parser: Parser::new(),
-        }
+        WriterBuilder::new().build(inner)
     }
 
     /// Unwraps this `Writer`, returning the underlying writer.
@@ -116,6 +352,105 @@ impl<W> Write for Writer<W>
     fn flush(&mut self) -> io::Result<()> { self.performer.flush() }
 }
 
+/// `Reader` wraps an underlying type that implements `Read`, stripping ANSI escape sequences
+/// from the bytes as they are read, before they reach the caller.
+///
+/// This is useful for matching against a child process's output (e.g. with `expect`-style
+/// line scanning) without having to buffer the whole stream up front: escape sequences are
+/// removed as each `read` call pulls bytes through.
+///
+/// # Example
+/// ```
+/// use std::io::{self, Read};
+/// use strip_ansi_escapes::Reader;
+///
+/// # fn foo() -> io::Result<()> {
+/// let bytes_with_colors = &b"\x1b[32mfoo\x1b[m bar"[..];
+/// let mut reader = Reader::new(bytes_with_colors);
+/// let mut plain_bytes = String::new();
+/// reader.read_to_string(&mut plain_bytes)?;
+/// assert_eq!(plain_bytes, "foo bar");
+/// # Ok(())
+/// # }
+/// ```
+pub struct Reader<R>
+    where R: Read,
+{
+    inner: R,
+    performer: Performer<Vec<u8>>,
+    parser: Parser,
+    raw_buf: [u8; 8192],
+    spill_pos: usize,
+}
+
+impl<R> Reader<R>
+    where R: Read,
+{
+    /// Create a new `Reader` that reads from `inner`.
+    pub fn new(inner: R) -> Reader<R> {
+        Reader {
+            inner,
+            performer: Performer {
+                writer: LineWriter::new(Vec::new()),
+                err: None,
+                config: Config::default(),
+            },
+            parser: Parser::new(),
+            raw_buf: [0; 8192],
+            spill_pos: 0,
+        }
+    }
+
+    /// Unwraps this `Reader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for Reader<R>
+    where R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            // Serve already-stripped bytes left over from a previous call first.
+            let spilled = &self.performer.writer.get_ref()[self.spill_pos..];
+            if !spilled.is_empty() {
+                let n = spilled.len().min(buf.len());
+                buf[..n].copy_from_slice(&spilled[..n]);
+                self.spill_pos += n;
+                if self.spill_pos == self.performer.writer.get_ref().len() {
+                    self.performer.writer.get_mut().clear();
+                    self.spill_pos = 0;
+                }
+                return Ok(n);
+            }
+
+            let read = self.inner.read(&mut self.raw_buf)?;
+            if read == 0 {
+                // No more raw bytes; flush whatever the parser is still holding on to
+                // (e.g. a buffered line with no trailing newline) and report EOF once
+                // that's drained too.
+                self.performer.flush()?;
+                if self.performer.writer.get_ref().is_empty() {
+                    return Ok(0);
+                }
+                continue;
+            }
+
+            for b in &self.raw_buf[..read] {
+                self.parser.advance(&mut self.performer, *b);
+            }
+            if let Some(e) = self.performer.err.take() {
+                return Err(e);
+            }
+            // Escape sequences can straddle `read` boundaries; the parser keeps any
+            // partial sequence internally, so only fully-decoded bytes reach the spill
+            // buffer here.
+            self.performer.flush()?;
+        }
+    }
+}
+
 impl<W> Performer<W>
     where W: Write,
 {
@@ -134,23 +469,63 @@ impl<W> Perform for Performer<W>
         self.err = write!(self.writer, "{}", c).err();
     }
     fn execute(&mut self, byte: u8) {
-        // We only care about executing linefeeds.
-        if byte == b'\n' {
-            self.err = writeln!(self.writer, "").err();
+        match byte {
+            // We always execute linefeeds.
+            b'\n' => self.err = writeln!(self.writer, "").err(),
+            // The rest of the C0 whitespace controls are only kept if asked for.
+            b'\t' | b'\r' | 0x0c if self.config.preserve_c0_whitespace => {
+                self.err = self.writer.write_all(&[byte]).err();
+            }
+            _ => {}
         }
     }
     // Since we're not actually implementing a terminal, we just ignore everything else.
     fn hook(&mut self, _params: &[i64], _intermediates: &[u8], _ignore: bool) {}
     fn put(&mut self, _byte: u8) {}
     fn unhook(&mut self) {}
-    fn osc_dispatch(&mut self, _params: &[&[u8]]) {}
+    fn osc_dispatch(&mut self, params: &[&[u8]]) {
+        if !self.config.preserve_osc8_hyperlinks || params.first() != Some(&&b"8"[..]) {
+            return;
+        }
+        // vte doesn't report whether the original sequence was BEL- or ST-terminated, so
+        // the reassembled sequence is always normalized to BEL below.
+        let mut reemit = || -> io::Result<()> {
+            write!(self.writer, "\x1b]")?;
+            for (i, param) in params.iter().enumerate() {
+                if i > 0 {
+                    write!(self.writer, ";")?;
+                }
+                self.writer.write_all(param)?;
+            }
+            write!(self.writer, "\x07")
+        };
+        self.err = reemit().err();
+    }
     fn csi_dispatch(
         &mut self,
-        _params: &[i64],
-        _intermediates: &[u8],
+        params: &[i64],
+        intermediates: &[u8],
         _ignore: bool,
-        _: char
-    ) {}
+        action: char
+    ) {
+        // Real SGR sequences never carry intermediate bytes, and vte doesn't tell us
+        // whether an intermediate came before or after the params, so bail rather than
+        // risk reassembling it in the wrong position.
+        if !self.config.preserve_sgr || action != 'm' || !intermediates.is_empty() {
+            return;
+        }
+        let mut reemit = || -> io::Result<()> {
+            write!(self.writer, "\x1b[")?;
+            for (i, param) in params.iter().enumerate() {
+                if i > 0 {
+                    write!(self.writer, ";")?;
+                }
+                write!(self.writer, "{}", param)?;
+            }
+            write!(self.writer, "m")
+        };
+        self.err = reemit().err();
+    }
     fn esc_dispatch(
         &mut self,
         _params: &[i64],
@@ -203,6 +578,48 @@ mod tests {
         assert_parsed(b"foo\nbar\n", b"foo\nbar\n");
     }
 
+    fn assert_parsed_via_reader(input: &[u8], expected: &[u8]) {
+        let mut reader = Reader::new(input);
+        let mut bytes = Vec::new();
+        io::Read::read_to_end(&mut reader, &mut bytes).expect("Failed to read stripped bytes");
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_reader_simple() {
+        assert_parsed_via_reader(
+            b"\x1b[m\x1b[m\x1b[32m\x1b[1m    Finished\x1b[m dev [unoptimized + debuginfo] target(s) in 0.0 secs",
+            b"    Finished dev [unoptimized + debuginfo] target(s) in 0.0 secs");
+    }
+
+    #[test]
+    fn test_reader_split_escape_across_reads() {
+        struct Chunked<'a> {
+            chunks: Vec<&'a [u8]>,
+        }
+
+        impl<'a> Read for Chunked<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.chunks.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.chunks.remove(0);
+                buf[..chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        // Split a single escape sequence ("\x1b[32m") across two separate reads from
+        // the inner reader.
+        let inner = Chunked {
+            chunks: vec![b"foo\x1b[3", b"2mbar"],
+        };
+        let mut reader = Reader::new(inner);
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).expect("Failed to read stripped bytes");
+        assert_eq!(bytes, b"foobar");
+    }
+
     #[test]
     fn test_escapes_newlines() {
         assert_parsed(b"\x1b[m\x1b[m\x1b[32m\x1b[1m   Compiling\x1b[m utf8parse v0.1.0
@@ -216,4 +633,95 @@ mod tests {
     Finished dev [unoptimized + debuginfo] target(s) in 0.66 secs
 ");
     }
+
+    #[test]
+    fn test_strip_bytes_incremental() {
+        let mut stripper = StripBytes::new();
+        let mut bytes = Vec::new();
+        bytes.extend(stripper.strip_next(b"\x1b[32mfoo").flatten().copied());
+        bytes.extend(stripper.strip_next(b"\x1b[m bar").flatten().copied());
+        assert_eq!(bytes, b"foo bar");
+    }
+
+    #[test]
+    fn test_strip_bytes_split_escape() {
+        let mut stripper = StripBytes::new();
+        let mut bytes = Vec::new();
+        // The escape sequence is split across the two calls.
+        bytes.extend(stripper.strip_next(b"foo\x1b[3").flatten().copied());
+        bytes.extend(stripper.strip_next(b"2mbar").flatten().copied());
+        assert_eq!(bytes, b"foobar");
+    }
+
+    #[test]
+    fn test_strip_bytes_clear() {
+        let mut stripper = StripBytes::new();
+        assert_eq!(
+            stripper.strip_next(b"foo").flatten().copied().collect::<Vec<u8>>(),
+            b"foo"
+        );
+        stripper.clear();
+        assert_eq!(
+            stripper.strip_next(b"bar").flatten().copied().collect::<Vec<u8>>(),
+            b"bar"
+        );
+    }
+
+    #[test]
+    fn test_strip_str() {
+        assert_eq!(strip_str("\x1b[32mfoo\x1b[m bar"), "foo bar");
+    }
+
+    #[test]
+    fn test_strip_str_incremental() {
+        let mut stripper = StripStr::new();
+        let mut s = String::new();
+        s.push_str(stripper.strip_next("\x1b[32mfoo"));
+        s.push_str(stripper.strip_next("\x1b[m bar"));
+        assert_eq!(s, "foo bar");
+    }
+
+    #[test]
+    fn test_strip_str_multibyte_char() {
+        assert_eq!(strip_str("\x1b[32m→ caf\u{e9}\x1b[m"), "→ café");
+    }
+
+    #[test]
+    fn test_writer_builder_default_strips_everything() {
+        let bytes = WriterBuilder::new()
+            .strip(b"foo\tbar\r\n\x1b[32mbaz\x1b[m")
+            .expect("Failed to strip escapes");
+        assert_eq!(bytes, b"foobar\nbaz");
+    }
+
+    #[test]
+    fn test_writer_builder_preserve_c0_whitespace() {
+        let bytes = WriterBuilder::new()
+            .preserve_c0_whitespace(true)
+            .strip(b"foo\tbar\r\nbaz")
+            .expect("Failed to strip escapes");
+        assert_eq!(bytes, b"foo\tbar\r\nbaz");
+    }
+
+    #[test]
+    fn test_writer_builder_preserve_sgr() {
+        let bytes = WriterBuilder::new()
+            .preserve_sgr(true)
+            .strip(b"\x1b[32mfoo\x1b[m bar")
+            .expect("Failed to strip escapes");
+        // vte reports the bare reset `\x1b[m` with a single `0` param.
+        assert_eq!(bytes, b"\x1b[32mfoo\x1b[0m bar");
+    }
+
+    #[test]
+    fn test_writer_builder_preserve_osc8_hyperlinks() {
+        let bytes = WriterBuilder::new()
+            .preserve_osc8_hyperlinks(true)
+            .strip(b"\x1b]8;;https://example.com\x07link\x1b]8;;\x07")
+            .expect("Failed to strip escapes");
+        assert_eq!(
+            bytes,
+            b"\x1b]8;;https://example.com\x07link\x1b]8;;\x07"
+        );
+    }
 }
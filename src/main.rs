@@ -1,12 +1,45 @@
-use std::{io, process};
+use std::io::{self, IsTerminal};
+use std::process;
 use strip_ansi_escapes::Writer;
 
 extern crate strip_ansi_escapes;
 
+enum StripMode {
+    Auto,
+    Always,
+    Never,
+}
+
+fn parse_args() -> StripMode {
+    let mut mode = StripMode::Auto;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--strip" => mode = StripMode::Always,
+            "--no-strip" => mode = StripMode::Never,
+            _ => {}
+        }
+    }
+    mode
+}
+
 fn main() {
-    let mut writer = Writer::new(io::stdout());
+    let mode = parse_args();
+    // Default to stripping only when stdout isn't a terminal, so colors survive on an
+    // interactive console but are removed when output is redirected to a file or pipe.
+    let should_strip = match mode {
+        StripMode::Always => true,
+        StripMode::Never => false,
+        StripMode::Auto => !io::stdout().is_terminal(),
+    };
+
+    let result = if should_strip {
+        let mut writer = Writer::new(io::stdout());
+        io::copy(&mut io::stdin(), &mut writer).map(|_| ())
+    } else {
+        io::copy(&mut io::stdin(), &mut io::stdout()).map(|_| ())
+    };
 
-    if let Err(error) = std::io::copy(&mut io::stdin(), &mut writer) {
+    if let Err(error) = result {
         eprintln!("I/O error copying stdin to stdout: {}", error);
         process::exit(1);
     }
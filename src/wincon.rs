@@ -0,0 +1,195 @@
+//! Windows console translation mode.
+//!
+//! Unlike [`Writer`][crate::Writer], which discards SGR color escapes outright, the types
+//! here translate them into calls against a Windows console handle as the remaining bytes
+//! are forwarded, so color information survives on terminals that don't interpret ANSI
+//! escapes themselves (this mirrors the approach anstream's `WinconBytes` takes).
+//!
+//! This module is only available with the `winconsole` feature enabled.
+
+use std::io::{self, Write};
+use vte::{Parser, Perform};
+
+/// One of the 16 colors addressable through `SetConsoleTextAttribute`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+/// Receives the console attribute changes decoded from SGR escape sequences.
+///
+/// Implement this for whatever owns the actual Windows console handle, translating each
+/// method into the matching `SetConsoleTextAttribute` call.
+pub trait WinConsole {
+    /// Reset all attributes to the console's default.
+    fn reset(&mut self) -> io::Result<()>;
+    /// Set the foreground color.
+    fn set_fg(&mut self, color: Color) -> io::Result<()>;
+    /// Set the background color.
+    fn set_bg(&mut self, color: Color) -> io::Result<()>;
+    /// Enable or disable bold/intensity.
+    fn set_bold(&mut self, bold: bool) -> io::Result<()>;
+}
+
+/// `WinconWriter` wraps an underlying `Write` plus a [`WinConsole`], translating SGR color
+/// escapes into console attribute changes instead of discarding them, while still removing
+/// every other escape category as [`Writer`][crate::Writer] does.
+///
+/// # Example
+/// ```no_run
+/// use std::io;
+/// use strip_ansi_escapes::wincon::{Color, WinConsole, WinconWriter};
+///
+/// struct MyConsole;
+///
+/// impl WinConsole for MyConsole {
+///     fn reset(&mut self) -> io::Result<()> { Ok(()) }
+///     fn set_fg(&mut self, _color: Color) -> io::Result<()> { Ok(()) }
+///     fn set_bg(&mut self, _color: Color) -> io::Result<()> { Ok(()) }
+///     fn set_bold(&mut self, _bold: bool) -> io::Result<()> { Ok(()) }
+/// }
+///
+/// # fn foo() -> io::Result<()> {
+/// use std::io::Write;
+/// let mut writer = WinconWriter::new(io::stdout(), MyConsole);
+/// writer.write_all(b"\x1b[32mfoo\x1b[m bar")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct WinconWriter<W, C>
+    where W: Write, C: WinConsole,
+{
+    performer: WinconPerformer<W, C>,
+    parser: Parser,
+}
+
+impl<W, C> WinconWriter<W, C>
+    where W: Write, C: WinConsole,
+{
+    /// Create a new `WinconWriter` that writes to `inner`, translating SGR sequences
+    /// through `console`.
+    pub fn new(inner: W, console: C) -> WinconWriter<W, C> {
+        WinconWriter {
+            performer: WinconPerformer {
+                writer: inner,
+                console,
+                err: None,
+            },
+            parser: Parser::new(),
+        }
+    }
+
+    /// Unwraps this `WinconWriter`, returning the underlying writer and console.
+    pub fn into_inner(self) -> (W, C) {
+        (self.performer.writer, self.performer.console)
+    }
+}
+
+impl<W, C> Write for WinconWriter<W, C>
+    where W: Write, C: WinConsole,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for b in buf.iter() {
+            self.parser.advance(&mut self.performer, *b)
+        }
+        match self.performer.err.take() {
+            Some(e) => Err(e),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.performer.writer.flush() }
+}
+
+struct WinconPerformer<W, C> {
+    writer: W,
+    console: C,
+    err: Option<io::Error>,
+}
+
+impl<W, C> Perform for WinconPerformer<W, C>
+    where W: Write, C: WinConsole,
+{
+    fn print(&mut self, c: char) {
+        self.err = write!(self.writer, "{}", c).err();
+    }
+    fn execute(&mut self, byte: u8) {
+        if byte == b'\n' {
+            self.err = writeln!(self.writer, "").err();
+        }
+    }
+    fn hook(&mut self, _params: &[i64], _intermediates: &[u8], _ignore: bool) {}
+    fn put(&mut self, _byte: u8) {}
+    fn unhook(&mut self) {}
+    fn osc_dispatch(&mut self, _params: &[&[u8]]) {}
+    fn csi_dispatch(
+        &mut self,
+        params: &[i64],
+        _intermediates: &[u8],
+        _ignore: bool,
+        action: char,
+    ) {
+        if action != 'm' {
+            return;
+        }
+        let params: &[i64] = if params.is_empty() { &[0] } else { params };
+        for &param in params {
+            let result = match param {
+                0 => self.console.reset(),
+                1 => self.console.set_bold(true),
+                22 => self.console.set_bold(false),
+                30..=37 => self.console.set_fg(sgr_color(param - 30)),
+                40..=47 => self.console.set_bg(sgr_color(param - 40)),
+                90..=97 => self.console.set_fg(sgr_color(param - 90 + 8)),
+                100..=107 => self.console.set_bg(sgr_color(param - 100 + 8)),
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                self.err = Some(e);
+            }
+        }
+    }
+    fn esc_dispatch(
+        &mut self,
+        _params: &[i64],
+        _intermediates: &[u8],
+        _ignore: bool,
+        _byte: u8
+    ) {}
+}
+
+fn sgr_color(n: i64) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::White,
+        8 => Color::BrightBlack,
+        9 => Color::BrightRed,
+        10 => Color::BrightGreen,
+        11 => Color::BrightYellow,
+        12 => Color::BrightBlue,
+        13 => Color::BrightMagenta,
+        14 => Color::BrightCyan,
+        _ => Color::BrightWhite,
+    }
+}
@@ -50,3 +50,49 @@ fn strip_escape_sequences() {
 
     assert_eq!(str::from_utf8(&output.stdout).unwrap(), "foobar");
 }
+
+#[test]
+fn no_strip_flag_passes_escape_sequences_through() {
+    let mut child = Command::new(executable_path("strip-ansi-escapes"))
+        .arg("--no-strip")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .as_mut()
+        .unwrap()
+        .write_all("foo\x1B7bar".as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+
+    assert_eq!(str::from_utf8(&output.stdout).unwrap(), "foo\x1B7bar");
+}
+
+#[test]
+fn strip_flag_strips_escape_sequences() {
+    let mut child = Command::new(executable_path("strip-ansi-escapes"))
+        .arg("--strip")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .as_mut()
+        .unwrap()
+        .write_all("foo\x1B7bar".as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+
+    assert_eq!(str::from_utf8(&output.stdout).unwrap(), "foobar");
+}